@@ -11,6 +11,7 @@ pub use self::control::Control;
 pub use self::crypto::Crypto;
 pub use self::debug_utils::DebugUtils;
 pub use self::dict_utils::DictUtils;
+pub use self::profiler::Profiler;
 pub use self::stack_utils::StackUtils;
 pub use self::string_utils::StringUtils;
 pub use self::vm_utils::VmUtils;
@@ -21,8 +22,10 @@ mod control;
 mod crypto;
 mod debug_utils;
 mod dict_utils;
+mod profiler;
 mod stack_utils;
 mod string_utils;
+mod text_format;
 mod vm_utils;
 
 pub struct BaseModule;
@@ -217,6 +220,20 @@ impl FiftModule for BaseModule {
         stack.push_int(len)
     }
 
+    #[cmd(name = "tuple>$", stack)]
+    fn interpret_tuple_to_string(stack: &mut Stack) -> Result<()> {
+        let tuple = stack.pop_tuple_owned()?;
+        let text = text_format::write_tuple(&tuple)?;
+        stack.push(text)
+    }
+
+    #[cmd(name = "$>tuple", stack)]
+    fn interpret_tuple_from_string(stack: &mut Stack) -> Result<()> {
+        let text = stack.pop_string()?;
+        let tuple = text_format::parse_tuple(&text, stack)?;
+        stack.push(tuple)
+    }
+
     #[cmd(name = "tuple", stack)]
     fn interpret_make_tuple(stack: &mut Stack) -> Result<()> {
         let n = stack.pop_smallint_range(0, 255)? as usize;
@@ -350,83 +367,230 @@ impl FiftModule for BaseModule {
         stack.push_bool(not_empty)
     }
 
+    #[cmd(name = "hmapmin", stack)]
+    fn interpret_hmap_min(stack: &mut Stack) -> Result<()> {
+        let map = stack.pop_hashmap()?;
+        let Some(mut node) = map.as_ref() else {
+            return stack.push_bool(false);
+        };
+        while let Some(left) = &node.left {
+            node = left;
+        }
+        stack.push_raw(node.key.stack_value.clone())?;
+        stack.push_raw(node.value.clone())?;
+        stack.push_bool(true)
+    }
+
+    #[cmd(name = "hmapmax", stack)]
+    fn interpret_hmap_max(stack: &mut Stack) -> Result<()> {
+        let map = stack.pop_hashmap()?;
+        let Some(mut node) = map.as_ref() else {
+            return stack.push_bool(false);
+        };
+        while let Some(right) = &node.right {
+            node = right;
+        }
+        stack.push_raw(node.key.stack_value.clone())?;
+        stack.push_raw(node.value.clone())?;
+        stack.push_bool(true)
+    }
+
+    #[cmd(name = "hmap@next", stack, args(forward = true))]
+    #[cmd(name = "hmap@prev", stack, args(forward = false))]
+    fn interpret_hmap_neighbor(stack: &mut Stack, forward: bool) -> Result<()> {
+        let map = stack.pop_hashmap()?;
+        let probe = HashMapTreeKey::new(stack.pop()?)?;
+
+        let mut node = map.as_ref();
+        let mut found: Option<&Rc<HashMapTreeNode>> = None;
+        while let Some(n) = node {
+            let is_candidate = if forward { n.key > probe } else { n.key < probe };
+            if is_candidate {
+                found = Some(n);
+                node = if forward { n.left.as_ref() } else { n.right.as_ref() };
+            } else {
+                node = if forward { n.right.as_ref() } else { n.left.as_ref() };
+            }
+        }
+
+        let Some(node) = found else {
+            return stack.push_bool(false);
+        };
+        stack.push_raw(node.key.stack_value.clone())?;
+        stack.push_raw(node.value.clone())?;
+        stack.push_bool(true)
+    }
+
+    #[cmd(name = "hmap>$", stack)]
+    fn interpret_hmap_to_string(stack: &mut Stack) -> Result<()> {
+        let map = stack.pop_hashmap()?;
+        let text = text_format::write_hmap(&map)?;
+        stack.push(text)
+    }
+
+    #[cmd(name = "$>hmap", stack)]
+    fn interpret_hmap_from_string(stack: &mut Stack) -> Result<()> {
+        let text = stack.pop_string()?;
+        let map = text_format::parse_hmap(&text, stack)?;
+        stack.push_opt_raw(map)
+    }
+
     #[cmd(name = "hmapforeach", tail)]
     fn interpret_hmap_foreach(ctx: &mut Context) -> Result<Option<Cont>> {
-        let func = ctx.stack.pop_cont_owned()?;
-        let Some(map) = ctx.stack.pop_hashmap()? else {
-            return Ok(None);
-        };
-        Ok(Some(Rc::new(cont::LoopCont::new(
-            HmapIterCont {
-                iter: map.owned_iter().peekable(),
-                ok: true,
-            },
-            func,
-            ctx.next.take(),
-        ))))
+        ctx.profiler.time("hmapforeach", || {
+            let func = ctx.stack.pop_cont_owned()?;
+            let Some(map) = ctx.stack.pop_hashmap()? else {
+                return Ok(None);
+            };
+            Ok(Some(Rc::new(cont::LoopCont::new(
+                HmapIterCont {
+                    iter: map.owned_iter().peekable(),
+                    ok: true,
+                },
+                func,
+                ctx.next.take(),
+            ))))
+        })
+    }
+
+    #[cmd(name = "hmapforeachrange", tail)]
+    fn interpret_hmap_foreach_range(ctx: &mut Context) -> Result<Option<Cont>> {
+        ctx.profiler.time("hmapforeachrange", || {
+            let func = ctx.stack.pop_cont_owned()?;
+            let hi = HashMapTreeKey::new(ctx.stack.pop()?)?;
+            let lo = HashMapTreeKey::new(ctx.stack.pop()?)?;
+            let Some(map) = ctx.stack.pop_hashmap()? else {
+                return Ok(None);
+            };
+            Ok(Some(Rc::new(cont::LoopCont::new(
+                HmapRangeIterCont {
+                    iter: map.owned_iter().peekable(),
+                    lo,
+                    hi,
+                    ok: true,
+                },
+                func,
+                ctx.next.take(),
+            ))))
+        })
     }
 
     // === Environment ===
+    //
+    // These commands (and the hashmap iteration words above) are the only
+    // ones timed via `ctx.profiler.time`. Wiring `Profiler::time` into the
+    // actual word-dispatch loop — so every word, including arithmetic/stack
+    // ops and user-defined words, is covered — would require a change in
+    // `crate::core`, outside this module. See `Profiler::time`'s doc comment.
 
     #[cmd(name = "now")]
     fn interpret_now(ctx: &mut Context) -> Result<()> {
-        ctx.stack.push_int(ctx.env.now_ms() / 1000)
+        ctx.profiler
+            .time("now", || ctx.stack.push_int(ctx.env.now_ms() / 1000))
     }
 
     #[cmd(name = "now_ms")]
     fn interpret_now_ms(ctx: &mut Context) -> Result<()> {
-        ctx.stack.push_int(ctx.env.now_ms())
+        ctx.profiler.time("now_ms", || ctx.stack.push_int(ctx.env.now_ms()))
     }
 
     #[cmd(name = "getenv")]
     fn interpret_getenv(ctx: &mut Context) -> Result<()> {
-        let name = ctx.stack.pop_string()?;
-        let value = ctx.env.get_env(&name).unwrap_or_default();
-        ctx.stack.push(value)
+        ctx.profiler.time("getenv", || {
+            let name = ctx.stack.pop_string()?;
+            let value = ctx.env.get_env(&name).unwrap_or_default();
+            ctx.stack.push(value)
+        })
     }
 
     #[cmd(name = "getenv?")]
     fn interpret_getenv_exists(ctx: &mut Context) -> Result<()> {
-        let name = ctx.stack.pop_string()?;
-        let exists = match ctx.env.get_env(&name) {
-            Some(value) => {
-                ctx.stack.push(value)?;
-                true
-            }
-            None => false,
-        };
-        ctx.stack.push_bool(exists)
+        ctx.profiler.time("getenv?", || {
+            let name = ctx.stack.pop_string()?;
+            let exists = match ctx.env.get_env(&name) {
+                Some(value) => {
+                    ctx.stack.push(value)?;
+                    true
+                }
+                None => false,
+            };
+            ctx.stack.push_bool(exists)
+        })
     }
 
     #[cmd(name = "file>B")]
     fn interpret_read_file(ctx: &mut Context) -> Result<()> {
-        let name = ctx.stack.pop_string()?;
-        let data = ctx.env.read_file(name.as_str())?;
-        ctx.stack.push(data)
+        ctx.profiler.time("file>B", || {
+            let name = ctx.stack.pop_string()?;
+            let data = ctx.env.read_file(name.as_str())?;
+            ctx.stack.push(data)
+        })
     }
 
     #[cmd(name = "filepart>B")]
     fn interpret_read_file_part(ctx: &mut Context) -> Result<()> {
-        let size = ctx.stack.pop_usize()? as u64;
-        let offset = ctx.stack.pop_usize()? as u64;
-        let name = ctx.stack.pop_string()?;
-        let data = ctx.env.read_file_part(name.as_str(), offset, size)?;
-        ctx.stack.push(data)
+        ctx.profiler.time("filepart>B", || {
+            let size = ctx.stack.pop_usize()? as u64;
+            let offset = ctx.stack.pop_usize()? as u64;
+            let name = ctx.stack.pop_string()?;
+            let data = ctx.env.read_file_part(name.as_str(), offset, size)?;
+            ctx.stack.push(data)
+        })
     }
 
     #[cmd(name = "B>file")]
     fn interpret_write_file(ctx: &mut Context) -> Result<()> {
-        let name = ctx.stack.pop_string()?;
-        let data = ctx.stack.pop_bytes()?;
-        ctx.env.write_file(name.as_str(), data.as_slice())?;
-        Ok(())
+        ctx.profiler.time("B>file", || {
+            let name = ctx.stack.pop_string()?;
+            let data = ctx.stack.pop_bytes()?;
+            ctx.env.write_file(name.as_str(), data.as_slice())?;
+            Ok(())
+        })
     }
 
     #[cmd(name = "file-exists?")]
     fn interpret_file_exists(ctx: &mut Context) -> Result<()> {
+        ctx.profiler.time("file-exists?", || {
+            let name = ctx.stack.pop_string()?;
+            let exists = ctx.env.file_exists(&name);
+            ctx.stack.push_bool(exists)
+        })
+    }
+
+    // === Profiling ===
+
+    #[cmd(name = "profile-on")]
+    fn interpret_profile_on(ctx: &mut Context) -> Result<()> {
+        ctx.profiler.enable();
+        Ok(())
+    }
+
+    #[cmd(name = "profile-off")]
+    fn interpret_profile_off(ctx: &mut Context) -> Result<()> {
+        ctx.profiler.disable();
+        Ok(())
+    }
+
+    #[cmd(name = ".profile-json")]
+    fn interpret_profile_json(ctx: &mut Context) -> Result<()> {
         let name = ctx.stack.pop_string()?;
-        let exists = ctx.env.file_exists(&name);
-        ctx.stack.push_bool(exists)
+        ctx.env.write_file(name.as_str(), ctx.profiler.to_json().as_bytes())
+    }
+
+    #[cmd(name = "profile-ratchet")]
+    fn interpret_profile_ratchet(ctx: &mut Context) -> Result<()> {
+        let baseline_file = ctx.stack.pop_string()?;
+        let noise_percent = ctx.stack.pop_smallint_range(0, 100)? as f64;
+
+        let baseline_data = ctx.env.read_file(baseline_file.as_str())?;
+        let baseline_json =
+            String::from_utf8(baseline_data).context("baseline profile is not valid UTF-8")?;
+
+        anyhow::ensure!(
+            !ctx.profiler.ratchet(&baseline_json, noise_percent)?,
+            "performance regression detected against baseline {baseline_file:?}"
+        );
+        Ok(())
     }
 }
 
@@ -458,3 +622,42 @@ impl cont::LoopContImpl for HmapIterCont {
         Ok(true)
     }
 }
+
+#[derive(Clone)]
+struct HmapRangeIterCont {
+    iter: Peekable<stack::HashMapTreeOwnedIter>,
+    lo: HashMapTreeKey,
+    hi: HashMapTreeKey,
+    ok: bool,
+}
+
+impl cont::LoopContImpl for HmapRangeIterCont {
+    fn pre_exec(&mut self, ctx: &mut Context) -> Result<bool> {
+        loop {
+            let entry = match self.iter.next() {
+                Some(entry) => entry,
+                None => return Ok(false),
+            };
+            if entry.key < self.lo {
+                continue;
+            }
+            if entry.key > self.hi {
+                return Ok(false);
+            }
+
+            ctx.stack.push_raw(entry.key.stack_value.clone())?;
+            ctx.stack.push_raw(entry.value.clone())?;
+            return Ok(true);
+        }
+    }
+
+    fn post_exec(&mut self, ctx: &mut Context) -> Result<bool> {
+        self.ok = ctx.stack.pop_bool()?;
+        Ok(self.ok && self.iter.peek().is_some())
+    }
+
+    fn finalize(&mut self, ctx: &mut Context) -> Result<bool> {
+        ctx.stack.push_bool(self.ok)?;
+        Ok(true)
+    }
+}
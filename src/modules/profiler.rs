@@ -0,0 +1,226 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context as _, Result};
+
+/// Aggregated invocation count and cumulative wall-clock time for a single word.
+#[derive(Default, Clone, Copy)]
+pub struct WordStats {
+    pub count: u64,
+    pub nanos: u128,
+}
+
+/// Opt-in profiler for word executions, attached to [`crate::core::Context`].
+///
+/// Disabled by default so normal interpretation pays no overhead; `profile-on`
+/// enables recording on every subsequent [`Profiler::time`] call. Coverage is
+/// only as complete as the call sites that invoke `time` — see its doc comment.
+#[derive(Default)]
+pub struct Profiler {
+    enabled: Cell<bool>,
+    stats: RefCell<HashMap<Rc<str>, WordStats>>,
+}
+
+impl Profiler {
+    pub fn enable(&self) {
+        self.enabled.set(true);
+    }
+
+    pub fn disable(&self) {
+        self.enabled.set(false);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    /// Times `f` and records the elapsed duration under `word`, unless profiling
+    /// is currently disabled. Call this from wherever `word` actually runs.
+    ///
+    /// Today that's the handful of `BaseModule` commands in
+    /// `src/modules/mod.rs` that opt in explicitly, not the word-dispatch
+    /// loop in `crate::core` — a module this crate's `src/modules` tree
+    /// doesn't reach. So `profile-ratchet` only catches regressions in the
+    /// words that call `time`, not arithmetic/stack ops or user-defined
+    /// words. Wiring this into dispatch itself would need a change to
+    /// `crate::core`, not `src/modules`.
+    pub fn time<T>(&self, word: &str, f: impl FnOnce() -> T) -> T {
+        if !self.is_enabled() {
+            return f();
+        }
+        let started = Instant::now();
+        let result = f();
+        self.record(word, started.elapsed());
+        result
+    }
+
+    pub fn record(&self, word: &str, elapsed: Duration) {
+        let mut stats = self.stats.borrow_mut();
+        match stats.get_mut(word) {
+            Some(entry) => {
+                entry.count += 1;
+                entry.nanos += elapsed.as_nanos();
+            }
+            None => {
+                stats.insert(
+                    Rc::from(word),
+                    WordStats {
+                        count: 1,
+                        nanos: elapsed.as_nanos(),
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let stats = self.stats.borrow();
+        let mut names: Vec<&Rc<str>> = stats.keys().collect();
+        names.sort();
+
+        let mut out = String::from("{\n");
+        for (i, name) in names.iter().enumerate() {
+            let WordStats { count, nanos } = stats[*name];
+            out.push_str(&format!(
+                "  {:?}: {{ \"count\": {count}, \"nanos\": {nanos} }}",
+                name.as_ref()
+            ));
+            if i + 1 < names.len() {
+                out.push(',');
+            }
+            out.push('\n');
+        }
+        out.push('}');
+        out
+    }
+
+    /// Compares the current stats against a previously saved baseline JSON
+    /// (as produced by [`Profiler::to_json`]), returning `true` if any word
+    /// present in the baseline got slower, per call, beyond `noise_percent`.
+    ///
+    /// The signal is average nanoseconds per call (`nanos / count`), not raw
+    /// `count` or raw `nanos` — both of those scale with how many times the
+    /// workload happens to invoke a word, which is a property of the input,
+    /// not of performance. A benchmark that simply calls `hmapforeach` twice
+    /// as often isn't a regression; a word that got slower per call is.
+    pub fn ratchet(&self, baseline_json: &str, noise_percent: f64) -> Result<bool> {
+        let baseline = parse_baseline(baseline_json)?;
+        let stats = self.stats.borrow();
+
+        let factor = 1.0 + noise_percent / 100.0;
+        let mut regressed = false;
+        for (name, base) in &baseline {
+            if base.count == 0 {
+                continue;
+            }
+            let Some(current) = stats.get(name.as_str()) else {
+                continue;
+            };
+            if current.count == 0 {
+                continue;
+            }
+
+            let base_avg_nanos = base.nanos as f64 / base.count as f64;
+            let current_avg_nanos = current.nanos as f64 / current.count as f64;
+            if current_avg_nanos > base_avg_nanos * factor {
+                regressed = true;
+            }
+        }
+
+        Ok(regressed)
+    }
+}
+
+/// Minimal recursive-descent reader for the flat object emitted by
+/// [`Profiler::to_json`]: `{ "word": { "count": N, "nanos": N }, ... }`.
+fn parse_baseline(json: &str) -> Result<HashMap<String, WordStats>> {
+    let mut chars = json.trim().chars().peekable();
+    let mut result = HashMap::new();
+
+    anyhow::ensure!(
+        chars.next() == Some('{'),
+        "expected '{{' at start of profile"
+    );
+
+    loop {
+        skip_ws(&mut chars);
+        if chars.peek() == Some(&'}') {
+            chars.next();
+            break;
+        }
+
+        let name = read_json_string(&mut chars).context("expected a word name")?;
+        skip_ws(&mut chars);
+        anyhow::ensure!(chars.next() == Some(':'), "expected ':' after word name");
+        skip_ws(&mut chars);
+        anyhow::ensure!(chars.next() == Some('{'), "expected '{{' for word stats");
+
+        let mut count = None;
+        let mut nanos = None;
+        loop {
+            skip_ws(&mut chars);
+            if chars.peek() == Some(&'}') {
+                chars.next();
+                break;
+            }
+            let key = read_json_string(&mut chars).context("expected a stat key")?;
+            skip_ws(&mut chars);
+            anyhow::ensure!(chars.next() == Some(':'), "expected ':' after stat key");
+            skip_ws(&mut chars);
+            let value = read_json_number(&mut chars).context("expected a numeric stat value")?;
+            match key.as_str() {
+                "count" => count = Some(value as u64),
+                "nanos" => nanos = Some(value),
+                other => anyhow::bail!("unknown profile stat key {other:?}"),
+            }
+            skip_ws(&mut chars);
+            if chars.peek() == Some(&',') {
+                chars.next();
+            }
+        }
+
+        result.insert(
+            name,
+            WordStats {
+                count: count.context("missing \"count\"")?,
+                nanos: nanos.context("missing \"nanos\"")?,
+            },
+        );
+
+        skip_ws(&mut chars);
+        if chars.peek() == Some(&',') {
+            chars.next();
+        }
+    }
+
+    Ok(result)
+}
+
+fn skip_ws(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn read_json_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<String> {
+    anyhow::ensure!(chars.next() == Some('"'), "expected a string");
+    let mut out = String::new();
+    loop {
+        match chars.next().context("unterminated string")? {
+            '"' => return Ok(out),
+            '\\' => out.push(chars.next().context("unterminated escape")?),
+            c => out.push(c),
+        }
+    }
+}
+
+fn read_json_number(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<u128> {
+    let mut digits = String::new();
+    while matches!(chars.peek(), Some(c) if c.is_ascii_digit()) {
+        digits.push(chars.next().unwrap());
+    }
+    anyhow::ensure!(!digits.is_empty(), "expected a number");
+    digits.parse().context("number out of range")
+}
@@ -0,0 +1,239 @@
+use std::iter::Peekable;
+use std::rc::Rc;
+use std::str::Chars;
+
+use anyhow::{Context as _, Result};
+use num_bigint::BigInt;
+
+use crate::core::*;
+
+/// Prints `map` as `HMAP{ k => v, ... }` in sorted key order, e.g.
+/// `HMAP{ 1 => "a", -5 => 'atom_foo, "k" => null }`. An empty map prints as
+/// `HMAP{ }`. Atom names are prefixed with `'` so they can't be confused with
+/// integers or `null` when read back.
+pub fn write_hmap(map: &Option<Rc<HashMapTreeNode>>) -> Result<String> {
+    let mut parts = Vec::new();
+    if let Some(map) = map {
+        for entry in map.clone().owned_iter() {
+            let mut part = String::new();
+            write_value(&mut part, &*entry.key.stack_value)?;
+            part.push_str(" => ");
+            write_value(&mut part, &*entry.value)?;
+            parts.push(part);
+        }
+    }
+    Ok(wrap("HMAP{", "}", &parts))
+}
+
+/// Prints `tuple` as `TUPLE[ v0, v1, ... ]`. An empty tuple prints as `TUPLE[ ]`.
+pub fn write_tuple(tuple: &[Rc<dyn StackValue>]) -> Result<String> {
+    let mut parts = Vec::with_capacity(tuple.len());
+    for item in tuple {
+        let mut part = String::new();
+        write_value(&mut part, &**item)?;
+        parts.push(part);
+    }
+    Ok(wrap("TUPLE[", "]", &parts))
+}
+
+fn wrap(open: &str, close: &str, parts: &[String]) -> String {
+    if parts.is_empty() {
+        format!("{open} {close}")
+    } else {
+        format!("{open} {} {close}", parts.join(", "))
+    }
+}
+
+fn write_value(out: &mut String, value: &dyn StackValue) -> Result<()> {
+    match value.ty() {
+        StackValueType::Null => out.push_str("null"),
+        StackValueType::Int => out.push_str(&value.as_int()?.to_string()),
+        StackValueType::String => write_quoted_string(out, value.as_string()?),
+        StackValueType::Atom => {
+            out.push('\'');
+            out.push_str(&value.as_atom()?.to_string());
+        }
+        StackValueType::Tuple => out.push_str(&write_tuple(value.as_tuple()?)?),
+        ty => anyhow::bail!("cannot serialize a value of type {ty:?} to text"),
+    }
+    Ok(())
+}
+
+/// Escapes only `"` and `\`, so that `read_quoted_string` (which decodes any
+/// `\<c>` back to the literal `c`) is an exact inverse — unlike `{:?}`, this
+/// never emits `\n`/`\t`/`\u{..}` escapes it couldn't also decode.
+fn write_quoted_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Parses the textual form produced by [`write_hmap`], reconstructing the tree
+/// via [`HashMapTreeNode::set`]. `HMAP{ }` parses to `None` (empty map).
+pub fn parse_hmap(text: &str, stack: &mut Stack) -> Result<Option<Rc<HashMapTreeNode>>> {
+    let mut reader = Reader::new(text);
+    reader.expect_str("HMAP{")?;
+
+    let mut map = None;
+    loop {
+        if reader.eat_char('}')? {
+            break;
+        }
+        let key_value = parse_value(&mut reader, stack)?;
+        let key = HashMapTreeKey::new(key_value)?;
+        reader.expect_str("=>")?;
+        let value = parse_value(&mut reader, stack)?;
+        HashMapTreeNode::set(&mut map, &key, &value);
+
+        if !reader.eat_char(',')? {
+            reader.expect_str("}")?;
+            break;
+        }
+    }
+    Ok(map)
+}
+
+/// Parses the textual form produced by [`write_tuple`].
+pub fn parse_tuple(text: &str, stack: &mut Stack) -> Result<StackTuple> {
+    let mut reader = Reader::new(text);
+    parse_tuple_body(&mut reader, stack)
+}
+
+fn parse_tuple_body(reader: &mut Reader, stack: &mut Stack) -> Result<StackTuple> {
+    reader.expect_str("TUPLE[")?;
+
+    let mut items = StackTuple::new();
+    loop {
+        if reader.eat_char(']')? {
+            break;
+        }
+        items.push(parse_value(reader, stack)?);
+
+        if !reader.eat_char(',')? {
+            reader.expect_str("]")?;
+            break;
+        }
+    }
+    Ok(items)
+}
+
+fn parse_value(reader: &mut Reader, stack: &mut Stack) -> Result<Rc<dyn StackValue>> {
+    if reader.peek_char() == Some('"') {
+        let s = reader.read_quoted_string()?;
+        stack.push(s)?;
+        return stack.pop();
+    }
+
+    if reader.peek_starts_with("TUPLE[") {
+        let tuple = parse_tuple_body(reader, stack)?;
+        stack.push(tuple)?;
+        return stack.pop();
+    }
+
+    // Atoms are always written `'name`, so a bare token below is unambiguously
+    // either `null` or an integer — it can never be mistaken for an atom whose
+    // name happens to look like one of those.
+    if reader.eat_char('\'')? {
+        let name = reader.read_token().context("expected an atom name")?;
+        let mut atom = stack.atoms().get(&name);
+        if atom.is_none() {
+            atom = Some(stack.atoms_mut().create_named(&name));
+        }
+        stack.push(atom.unwrap())?;
+        return stack.pop();
+    }
+
+    let token = reader.read_token().context("expected a value")?;
+    if token == "null" {
+        stack.push_null()?;
+        return stack.pop();
+    }
+
+    let n: BigInt = token
+        .parse()
+        .with_context(|| format!("expected an atom, \"null\", or an integer, found {token:?}"))?;
+    stack.push_int(n)?;
+    stack.pop()
+}
+
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.chars.peek(), Some(c) if c.is_whitespace()) {
+            self.chars.next();
+        }
+    }
+
+    fn peek_char(&mut self) -> Option<char> {
+        self.skip_ws();
+        self.chars.peek().copied()
+    }
+
+    fn peek_starts_with(&mut self, prefix: &str) -> bool {
+        self.skip_ws();
+        let mut iter = self.chars.clone();
+        prefix.chars().all(|expected| iter.next() == Some(expected))
+    }
+
+    fn eat_char(&mut self, c: char) -> Result<bool> {
+        if self.peek_char() == Some(c) {
+            self.chars.next();
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn expect_str(&mut self, lit: &str) -> Result<()> {
+        self.skip_ws();
+        for expected in lit.chars() {
+            anyhow::ensure!(
+                self.chars.next() == Some(expected),
+                "expected {lit:?} in textual map/tuple literal"
+            );
+        }
+        Ok(())
+    }
+
+    fn read_quoted_string(&mut self) -> Result<String> {
+        self.expect_str("\"")?;
+        let mut out = String::new();
+        loop {
+            match self.chars.next().context("unterminated string literal")? {
+                '"' => return Ok(out),
+                '\\' => out.push(self.chars.next().context("unterminated escape")?),
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn read_token(&mut self) -> Option<String> {
+        self.skip_ws();
+        let mut out = String::new();
+        while matches!(self.chars.peek(), Some(c) if !is_token_terminator(*c)) {
+            out.push(self.chars.next().unwrap());
+        }
+        (!out.is_empty()).then_some(out)
+    }
+}
+
+fn is_token_terminator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, ',' | '}' | ']' | '[')
+}